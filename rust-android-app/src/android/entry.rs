@@ -0,0 +1,39 @@
+/// Declares the native entry point Android's `android-activity` glue looks
+/// for (`android_main(app: AndroidApp)`, exported as `#[no_mangle]`),
+/// builds the app's [`super::InputHandler`] from it, and starts the
+/// winit-driven render loop.
+///
+/// `$build_handler` is an `Fn(&AndroidApp) -> H` (using the `AndroidApp`
+/// type `winit` itself re-exports at
+/// `winit::platform::android::activity::AndroidApp`, rather than a direct
+/// `android-activity` dependency, per `winit`'s own guidance on avoiding a
+/// glue-crate version mismatch) - it receives the live `AndroidApp` (so it
+/// can wire up
+/// [`crate::context::AndroidContext::new`] and [`crate::telemetry::init`],
+/// which both need it) and is called *before* the event loop starts, so
+/// all of that setup - and the per-module logger installed here - is in
+/// place for the very first frame and the very first forwarded input
+/// event, not just for whatever runs after the activity has already torn
+/// down.
+///
+/// This intentionally doesn't wrap a conventional `fn main()`: on Android,
+/// `winit`'s event loop takes over the main thread for the activity's
+/// entire lifetime via `run_app`, so there is no "after the loop" for a
+/// wrapped `main` to run that isn't also "after the activity is dead."
+/// `$build_handler` plays that role instead, running once, up front, with
+/// a live `AndroidApp` to build against.
+///
+/// ```ignore
+/// gpui_mobile::android_main!(|app| MyApp::new(app));
+/// ```
+#[macro_export]
+macro_rules! android_main {
+    ($build_handler:expr) => {
+        #[no_mangle]
+        fn android_main(app: ::winit::platform::android::activity::AndroidApp) {
+            $crate::logging::init();
+            let handler = ($build_handler)(&app);
+            $crate::android::run_event_loop(app, handler);
+        }
+    };
+}
@@ -0,0 +1,49 @@
+use winit::event::{ElementState, KeyEvent, TouchPhase, WindowEvent};
+
+/// A touch/keyboard/IME event forwarded from the Android window into
+/// GPUI's input system.
+///
+/// This is a thin, GPUI-independent representation so this crate doesn't
+/// need to depend on GPUI's internal event types; the caller's
+/// [`InputHandler`] is responsible for translating these into whatever
+/// `gpui::PlatformInput` expects.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MobileInputEvent {
+    Touch {
+        id: u64,
+        x: f64,
+        y: f64,
+        phase: TouchPhase,
+    },
+    Key {
+        key_event: KeyEvent,
+        state: ElementState,
+    },
+    ImeCommit(String),
+}
+
+/// Implemented by the host application to receive forwarded input.
+pub trait InputHandler {
+    fn on_input(&mut self, event: MobileInputEvent);
+}
+
+/// Converts a `winit` window event into our GPUI-facing representation, if
+/// it carries input the app cares about.
+pub fn convert_window_event(event: &WindowEvent) -> Option<MobileInputEvent> {
+    match event {
+        WindowEvent::Touch(touch) => Some(MobileInputEvent::Touch {
+            id: touch.id,
+            x: touch.location.x,
+            y: touch.location.y,
+            phase: touch.phase,
+        }),
+        WindowEvent::KeyboardInput { event, .. } => Some(MobileInputEvent::Key {
+            key_event: event.clone(),
+            state: event.state,
+        }),
+        WindowEvent::Ime(winit::event::Ime::Commit(text)) => {
+            Some(MobileInputEvent::ImeCommit(text.clone()))
+        }
+        _ => None,
+    }
+}
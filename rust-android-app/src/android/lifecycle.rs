@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use winit::application::ApplicationHandler;
+use winit::event::WindowEvent;
+use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::platform::android::activity::AndroidApp;
+use winit::platform::android::EventLoopBuilderExtAndroid;
+use winit::window::{Window, WindowId};
+
+use super::input::{self, InputHandler};
+use super::renderer::Renderer;
+
+/// Drives the Android main-thread event loop for the lifetime of the
+/// activity, owning the `wgpu` surface and forwarding input to `handler`.
+///
+/// `winit`'s Android backend maps `NativeActivity`/`GameActivity` lifecycle
+/// callbacks onto [`ApplicationHandler::resumed`] / `suspended`: `resumed`
+/// fires once a window (and therefore an `ANativeWindow`) is available, and
+/// `suspended` fires when Android tears it down (rotation, multi-window,
+/// backgrounding). The `wgpu` surface is created fresh in every `resumed`
+/// and dropped in every `suspended` — the old `ANativeWindow` is invalid the
+/// moment `suspended` fires, so rendering must stop immediately rather than
+/// racing the next frame.
+pub fn run_event_loop(app: AndroidApp, handler: impl InputHandler + 'static) {
+    let event_loop = EventLoop::builder()
+        .with_android_app(app)
+        .build()
+        .expect("failed to create winit event loop on Android main thread");
+
+    let mut app = App {
+        window: None,
+        renderer: None,
+        handler,
+        resumed: false,
+    };
+    event_loop
+        .run_app(&mut app)
+        .expect("android event loop exited unexpectedly");
+}
+
+struct App<H: InputHandler> {
+    window: Option<Arc<Window>>,
+    renderer: Option<Renderer>,
+    handler: H,
+    resumed: bool,
+}
+
+impl<H: InputHandler> ApplicationHandler for App<H> {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        let window = Arc::new(
+            event_loop
+                .create_window(Window::default_attributes())
+                .expect("failed to create Android window"),
+        );
+        self.renderer = Some(Renderer::new_from_window(Arc::clone(&window)));
+        self.window = Some(window);
+        self.resumed = true;
+    }
+
+    fn suspended(&mut self, _event_loop: &ActiveEventLoop) {
+        // The ANativeWindow backing this surface is gone: drop it now
+        // rather than risk submitting to it on the next frame.
+        self.resumed = false;
+        self.renderer = None;
+        self.window = None;
+        // The app is backgrounding: flush telemetry now rather than
+        // waiting for the next scheduled interval, since there's no
+        // guarantee the process survives until then. A no-op if
+        // telemetry was never initialized or is disabled.
+        crate::telemetry::flush();
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
+        match event {
+            WindowEvent::CloseRequested | WindowEvent::Destroyed => {
+                self.renderer = None;
+                event_loop.exit();
+            }
+            WindowEvent::Resized(size) => {
+                if let Some(renderer) = self.renderer.as_mut() {
+                    renderer.resize(size.width, size.height);
+                }
+            }
+            WindowEvent::RedrawRequested => {
+                if self.resumed {
+                    if let Some(renderer) = self.renderer.as_mut() {
+                        renderer.render();
+                    }
+                }
+            }
+            other => {
+                if let Some(mobile_event) = input::convert_window_event(&other) {
+                    self.handler.on_input(mobile_event);
+                }
+            }
+        }
+    }
+
+    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
+        if self.resumed {
+            if let Some(window) = self.window.as_ref() {
+                window.request_redraw();
+            }
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! Android runtime glue: lifecycle handling, window/surface management, and
+//! the event loop that drives rendering.
+//!
+//! This module wraps `android-activity` + `winit` + `wgpu` so that a GPUI
+//! app can be entered via [`crate::android_main!`] instead of the bare JNI
+//! stub the crate used to expose. The `wgpu` surface is owned by
+//! [`Renderer`], which recreates it for every new window and clears and
+//! presents it each frame; it is not handed to the app.
+
+mod entry;
+mod input;
+mod lifecycle;
+mod renderer;
+
+pub use input::{InputHandler, MobileInputEvent};
+pub use lifecycle::run_event_loop;
+pub use renderer::Renderer;
+
+// `android_main!` is exported at the crate root via `#[macro_export]`
+// (see `entry.rs`); nothing further to re-export here.
@@ -0,0 +1,113 @@
+use std::sync::{OnceLock, RwLock};
+
+use jni::objects::{GlobalRef, JClass, JObject, JValue};
+use jni::{JNIEnv, JavaVM};
+
+/// Cached by `JNI_OnLoad` so Rust code running off the JNI call stack (e.g.
+/// a background thread or a `winit` callback) can still attach and call
+/// back into Java.
+static JVM: OnceLock<JavaVM> = OnceLock::new();
+
+/// Global ref to the Kotlin object whose methods we invoke for
+/// Rust -> Kotlin calls. An `RwLock` rather than a `OnceLock` because the
+/// Activity that registers it is recreated across rotation/process
+/// restart, and re-registration needs to actually replace the stale ref
+/// rather than silently no-op (same pattern as
+/// [`crate::logging`]'s `LEVEL_OVERRIDES`).
+static CALLBACK: RwLock<Option<GlobalRef>> = RwLock::new(None);
+
+/// A handle for delivering events/results from Rust to the Kotlin side
+/// asynchronously, without needing a live `JNIEnv` from an existing JNI
+/// call.
+pub struct JavaCallback;
+
+impl JavaCallback {
+    /// Calls `void onEvent(String method, byte[] payload)` on the
+    /// registered callback object, attaching the current thread to the JVM
+    /// first if it isn't already attached.
+    ///
+    /// Any pending Java exception is logged and cleared rather than left to
+    /// propagate, since there is no JNI caller above us to receive it.
+    pub fn send(method: &str, payload: &[u8]) {
+        let Some(vm) = JVM.get() else {
+            log::error!("send(`{method}`): JavaVM not initialized (JNI_OnLoad never ran?)");
+            return;
+        };
+        let Some(callback) = CALLBACK
+            .read()
+            .expect("callback lock poisoned")
+            .clone()
+        else {
+            log::warn!("send(`{method}`): no callback registered, dropping event");
+            return;
+        };
+
+        let mut env = match vm.attach_current_thread() {
+            Ok(env) => env,
+            Err(err) => {
+                log::error!("send(`{method}`): failed to attach thread: {err}");
+                return;
+            }
+        };
+
+        let method_name = match env.new_string(method) {
+            Ok(s) => s,
+            Err(err) => {
+                log::error!("send(`{method}`): could not allocate method name: {err}");
+                return;
+            }
+        };
+        let args = match env.byte_array_from_slice(payload) {
+            Ok(a) => a,
+            Err(err) => {
+                log::error!("send(`{method}`): could not allocate payload: {err}");
+                return;
+            }
+        };
+
+        let result = env.call_method(
+            callback.as_obj(),
+            "onEvent",
+            "(Ljava/lang/String;[B)V",
+            &[JValue::from(&method_name), JValue::from(&args)],
+        );
+
+        if let Err(err) = result {
+            log::error!("send(`{method}`): onEvent call failed: {err}");
+        }
+        if env.exception_check().unwrap_or(false) {
+            let _ = env.exception_describe();
+            let _ = env.exception_clear();
+        }
+    }
+}
+
+/// Called from `JNI_OnLoad` to cache the `JavaVM` for later
+/// `attach_current_thread` calls.
+pub(crate) fn init(vm: JavaVM) {
+    let _ = JVM.set(vm);
+}
+
+/// Returns the `JavaVM` cached at `JNI_OnLoad`, if it has run yet. Used by
+/// anything that needs to call into Java from off the JNI call stack, such
+/// as [`crate::logging`].
+pub(crate) fn cached_vm() -> Option<&'static JavaVM> {
+    JVM.get()
+}
+
+/// `Java_com_example_rustapp_NativeLib_setCallback`: registers the Kotlin
+/// object that receives Rust -> Kotlin events, taking a global ref so it
+/// survives past the current JNI call.
+#[no_mangle]
+pub extern "system" fn Java_com_example_rustapp_NativeLib_setCallback(
+    env: JNIEnv,
+    _class: JClass,
+    callback: JObject,
+) {
+    match env.new_global_ref(callback) {
+        Ok(global) => {
+            *CALLBACK.write().expect("callback lock poisoned") = Some(global);
+        }
+        Err(err) => log::error!("setCallback: failed to create global ref: {err}"),
+    }
+}
@@ -0,0 +1,70 @@
+//! A structured method channel between the Kotlin/Java layer and Rust,
+//! replacing one-off JNI functions with a single dispatch point in each
+//! direction.
+//!
+//! Rust -> Kotlin calls and Kotlin -> Rust calls are both payload-based:
+//! callers pass a method name and a byte-serialized argument. Kotlin -> Rust
+//! calls get back a byte-serialized result; Rust -> Kotlin calls (via
+//! [`JavaCallback::send`]) are one-way events with no result. This mirrors
+//! Flutter's `MethodChannel` and means adding a new call doesn't require a
+//! new `Java_..._NativeLib_*` export.
+
+mod callback;
+mod registry;
+
+pub use callback::JavaCallback;
+pub(crate) use callback::cached_vm;
+pub use registry::{register, MethodRegistry, CHANNEL};
+
+use std::os::raw::c_void;
+
+use jni::objects::{JByteArray, JClass, JString};
+use jni::sys::{jbyteArray, jint, JNI_VERSION_1_6};
+use jni::{JNIEnv, JavaVM};
+
+/// Caches the `JavaVM` for later `attach_current_thread` calls (see
+/// [`callback::JavaCallback`]), since JNI only hands it to us once, here.
+#[no_mangle]
+pub extern "system" fn JNI_OnLoad(vm: JavaVM, _reserved: *mut c_void) -> jint {
+    callback::init(vm);
+    JNI_VERSION_1_6
+}
+
+/// `Java_com_example_rustapp_NativeLib_invoke`: dispatches a Kotlin -> Rust
+/// call by method name through the global [`MethodRegistry`].
+///
+/// Returns an empty byte array (rather than throwing) when `method` has no
+/// registered handler, so a version-skew between the Kotlin and Rust sides
+/// degrades gracefully instead of crashing the JVM.
+#[no_mangle]
+pub extern "system" fn Java_com_example_rustapp_NativeLib_invoke(
+    mut env: JNIEnv,
+    _class: JClass,
+    method: JString,
+    args: JByteArray,
+) -> jbyteArray {
+    let method: String = match env.get_string(&method) {
+        Ok(s) => s.into(),
+        Err(err) => {
+            log::error!("invoke: invalid method name: {err}");
+            return std::ptr::null_mut();
+        }
+    };
+    let payload = match env.convert_byte_array(&args) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            log::error!("invoke: could not read args for `{method}`: {err}");
+            return std::ptr::null_mut();
+        }
+    };
+
+    let result = CHANNEL.dispatch(&method, &payload).unwrap_or_default();
+
+    match env.byte_array_from_slice(&result) {
+        Ok(array) => array.into_raw(),
+        Err(err) => {
+            log::error!("invoke: could not allocate result for `{method}`: {err}");
+            std::ptr::null_mut()
+        }
+    }
+}
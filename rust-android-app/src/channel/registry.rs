@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+/// A handler for one method name: takes the raw argument payload and
+/// returns a raw result payload.
+///
+/// Payloads are opaque bytes (JSON-encoded by convention) rather than a
+/// fixed Rust type, since the registry has no way to know what shape each
+/// method's caller expects - callers and handlers agree on the encoding out
+/// of band, the same way a Flutter `MethodChannel` handler does.
+type Handler = Box<dyn Fn(&[u8]) -> Vec<u8> + Send + Sync>;
+
+/// Global dispatch table for Kotlin -> Rust calls, keyed by method name.
+#[derive(Default)]
+pub struct MethodRegistry {
+    handlers: RwLock<HashMap<String, Handler>>,
+}
+
+impl MethodRegistry {
+    /// Registers `handler` for `method`, replacing any previous handler for
+    /// the same name.
+    pub fn register(&self, method: impl Into<String>, handler: impl Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static) {
+        self.handlers
+            .write()
+            .expect("method registry lock poisoned")
+            .insert(method.into(), Box::new(handler));
+    }
+
+    /// Dispatches `payload` to the handler registered for `method`, if any.
+    pub fn dispatch(&self, method: &str, payload: &[u8]) -> Option<Vec<u8>> {
+        let handlers = self.handlers.read().expect("method registry lock poisoned");
+        let handler = handlers.get(method)?;
+        Some(handler(payload))
+    }
+}
+
+/// The process-wide method channel shared by all JNI entry points.
+pub static CHANNEL: LazyLock<MethodRegistry> = LazyLock::new(MethodRegistry::default);
+
+/// Registers a handler for `method` on the global [`CHANNEL`].
+pub fn register(method: impl Into<String>, handler: impl Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static) {
+    CHANNEL.register(method, handler);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MethodRegistry;
+
+    #[test]
+    fn dispatch_miss_returns_none() {
+        let registry = MethodRegistry::default();
+        assert_eq!(registry.dispatch("nope", b""), None);
+    }
+
+    #[test]
+    fn dispatch_calls_registered_handler_with_payload() {
+        let registry = MethodRegistry::default();
+        registry.register("echo", |payload| payload.to_vec());
+        assert_eq!(registry.dispatch("echo", b"hello"), Some(b"hello".to_vec()));
+    }
+
+    #[test]
+    fn register_replaces_existing_handler_for_same_method() {
+        let registry = MethodRegistry::default();
+        registry.register("greet", |_| b"first".to_vec());
+        registry.register("greet", |_| b"second".to_vec());
+        assert_eq!(registry.dispatch("greet", b""), Some(b"second".to_vec()));
+    }
+}
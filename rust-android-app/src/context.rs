@@ -0,0 +1,259 @@
+//! A safe wrapper around the Android `Context`, for reading app directories,
+//! assets, and system services from Rust without hand-rolled JNI.
+//!
+//! Raw JNI calls against `Context` are a well-known crash source: calling
+//! into Java without attaching the current thread, or leaving a pending
+//! Java exception unchecked, aborts the process rather than producing a
+//! recoverable error. Every accessor here checks for (and clears) a pending
+//! exception before returning, so callers get an [`AndroidError`] instead
+//! of a SIGABRT. The accessors attach the current thread themselves, except
+//! [`AndroidContext::system_service`], which runs on the `JNIEnv` its caller
+//! passes in; [`AndroidContext::new`] likewise needs a thread that is
+//! already attached.
+
+use std::fmt;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use jni::objects::{GlobalRef, JObject, JString, JValue, JValueOwned};
+use jni::JavaVM;
+
+/// Errors surfaced by [`AndroidContext`] in place of letting a Java
+/// exception escape and abort the process.
+#[derive(Debug)]
+pub enum AndroidError {
+    /// The current thread could not be attached to the `JavaVM`.
+    AttachFailed(jni::errors::Error),
+    /// A JNI call failed (method not found, wrong signature, etc.).
+    JniCall(jni::errors::Error),
+    /// The JNI call raised a Java exception, which has been cleared.
+    JavaException(String),
+}
+
+impl fmt::Display for AndroidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AttachFailed(err) => write!(f, "failed to attach thread to JavaVM: {err}"),
+            Self::JniCall(err) => write!(f, "JNI call failed: {err}"),
+            Self::JavaException(message) => write!(f, "Java exception: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for AndroidError {}
+
+/// A safe, typed accessor for the Activity/Application `Context`, captured
+/// once (as a global ref) in the activity-created callback and reusable
+/// from any thread afterwards.
+#[derive(Clone)]
+pub struct AndroidContext {
+    // `JavaVM` itself isn't `Clone` (it's a raw pointer wrapper around a
+    // process-wide singleton), so it's kept behind an `Arc` purely so
+    // `AndroidContext` can be - callers like `telemetry` hand clones of
+    // this around freely rather than threading a reference everywhere.
+    vm: Arc<JavaVM>,
+    context: GlobalRef,
+}
+
+impl AndroidContext {
+    /// Takes a global ref on `context` so it outlives the JNI call it was
+    /// obtained from.
+    pub fn new(vm: JavaVM, context: &JObject) -> Result<Self, AndroidError> {
+        let env = vm.get_env().map_err(AndroidError::AttachFailed)?;
+        let context = env.new_global_ref(context).map_err(AndroidError::JniCall)?;
+        Ok(Self {
+            vm: Arc::new(vm),
+            context,
+        })
+    }
+
+    pub fn files_dir(&self) -> Result<PathBuf, AndroidError> {
+        self.call_path_method("getFilesDir")
+    }
+
+    pub fn cache_dir(&self) -> Result<PathBuf, AndroidError> {
+        self.call_path_method("getCacheDir")
+    }
+
+    pub fn native_library_dir(&self) -> Result<PathBuf, AndroidError> {
+        let mut env = self.attach()?;
+        let app_info = self
+            .checked_call(
+                &mut env,
+                "getApplicationInfo",
+                "()Landroid/content/pm/ApplicationInfo;",
+                &[],
+            )?
+            .l()
+            .map_err(AndroidError::JniCall)?;
+        let path = env.get_field(&app_info, "nativeLibraryDir", "Ljava/lang/String;");
+        self.check_exception(&mut env)?;
+        let path = path
+            .map_err(AndroidError::JniCall)?
+            .l()
+            .map_err(AndroidError::JniCall)?;
+        let path: String = env
+            .get_string(&JString::from(path))
+            .map_err(AndroidError::JniCall)?
+            .into();
+        Ok(PathBuf::from(path))
+    }
+
+    /// Opens an asset from `assets/` via `AssetManager`, returning a plain
+    /// `Read` so callers don't need to touch JNI types.
+    pub fn open_asset(&self, name: &str) -> Result<impl Read, AndroidError> {
+        let mut env = self.attach()?;
+        let asset_manager = self
+            .checked_call(
+                &mut env,
+                "getAssets",
+                "()Landroid/content/res/AssetManager;",
+                &[],
+            )?
+            .l()
+            .map_err(AndroidError::JniCall)?;
+        let jname = env.new_string(name).map_err(AndroidError::JniCall)?;
+        let stream = env.call_method(
+            &asset_manager,
+            "open",
+            "(Ljava/lang/String;)Ljava/io/InputStream;",
+            &[JValue::from(&jname)],
+        );
+        self.check_exception(&mut env)?;
+        let stream = stream
+            .map_err(AndroidError::JniCall)?
+            .l()
+            .map_err(AndroidError::JniCall)?;
+
+        // Drain eagerly into memory: there is no safe, zero-copy way to
+        // hand out a `java.io.InputStream` as a borrowed `Read` without
+        // keeping the `JNIEnv` alive for the reader's lifetime.
+        //
+        // Read through the `read(byte[])` overload into a reusable chunk
+        // buffer rather than `read()` one byte at a time - the latter costs
+        // one JNI round-trip per byte, which is unusably slow for any asset
+        // larger than a few bytes.
+        const CHUNK_SIZE: usize = 8192;
+        let chunk_array = env
+            .new_byte_array(CHUNK_SIZE as i32)
+            .map_err(AndroidError::JniCall)?;
+        let mut chunk_buf = vec![0i8; CHUNK_SIZE];
+        let mut buf = Vec::new();
+        loop {
+            let read = env.call_method(&stream, "read", "([B)I", &[JValue::from(&chunk_array)]);
+            self.check_exception(&mut env)?;
+            let read = read
+                .map_err(AndroidError::JniCall)?
+                .i()
+                .map_err(AndroidError::JniCall)?;
+            if read < 0 {
+                break;
+            }
+            let read = read as usize;
+            env.get_byte_array_region(&chunk_array, 0, &mut chunk_buf[..read])
+                .map_err(AndroidError::JniCall)?;
+            buf.extend(chunk_buf[..read].iter().map(|&b| b as u8));
+        }
+        Ok(io::Cursor::new(buf))
+    }
+
+    /// Returns the raw `JObject` for a system service (e.g.
+    /// `"vibrator"`, `"connectivity"`) fetched via `getSystemService`.
+    ///
+    /// The object is returned as a local ref valid for the current JNI
+    /// call; callers needing to hold onto it across calls should take
+    /// their own global ref.
+    pub fn system_service<'local>(
+        &self,
+        env: &mut jni::JNIEnv<'local>,
+        name: &str,
+    ) -> Result<JObject<'local>, AndroidError> {
+        let jname = env.new_string(name).map_err(AndroidError::JniCall)?;
+        let service = env.call_method(
+            self.context.as_obj(),
+            "getSystemService",
+            "(Ljava/lang/String;)Ljava/lang/Object;",
+            &[JValue::from(&jname)],
+        );
+        self.check_exception(env)?;
+        service
+            .map_err(AndroidError::JniCall)?
+            .l()
+            .map_err(AndroidError::JniCall)
+    }
+
+    fn call_path_method(&self, method: &str) -> Result<PathBuf, AndroidError> {
+        let mut env = self.attach()?;
+        let dir = self
+            .checked_call(&mut env, method, "()Ljava/io/File;", &[])?
+            .l()
+            .map_err(AndroidError::JniCall)?;
+        let path = env.call_method(&dir, "getAbsolutePath", "()Ljava/lang/String;", &[]);
+        self.check_exception(&mut env)?;
+        let path = path
+            .map_err(AndroidError::JniCall)?
+            .l()
+            .map_err(AndroidError::JniCall)?;
+        let path: String = env
+            .get_string(&JString::from(path))
+            .map_err(AndroidError::JniCall)?
+            .into();
+        Ok(PathBuf::from(path))
+    }
+
+    /// Attaches the current thread to the `JavaVM`. Every public accessor
+    /// except `system_service` (which uses the caller's `JNIEnv`) goes
+    /// through this rather than assuming it's already called from a JNI
+    /// entry point, so those accessors work from any thread (e.g. a
+    /// background render or network thread).
+    fn attach(&self) -> Result<jni::AttachGuard<'_>, AndroidError> {
+        self.vm.attach_current_thread().map_err(AndroidError::AttachFailed)
+    }
+
+    fn checked_call<'e>(
+        &self,
+        env: &mut jni::JNIEnv<'e>,
+        method: &str,
+        sig: &str,
+        args: &[JValue],
+    ) -> Result<JValueOwned<'e>, AndroidError> {
+        // Check for a pending exception before propagating the call's own
+        // error: on a Java exception `call_method` returns
+        // `Err(JavaException)` and leaves the exception pending.
+        let result = env.call_method(self.context.as_obj(), method, sig, args);
+        self.check_exception(env)?;
+        result.map_err(AndroidError::JniCall)
+    }
+
+    /// Checks for and clears a pending Java exception, turning it into an
+    /// `Err` instead of letting it propagate back across the JNI boundary
+    /// (which would otherwise abort on the next JNI call).
+    fn check_exception(&self, env: &mut jni::JNIEnv) -> Result<(), AndroidError> {
+        if env.exception_check().unwrap_or(false) {
+            // The JNI spec forbids calling almost anything other than
+            // Exception{Occurred,Describe,Clear,Check} while an exception is
+            // pending - `toString` below would be undefined behavior on a
+            // real JVM/ART if called before this.
+            let throwable = env.exception_occurred().ok();
+            let _ = env.exception_clear();
+            let message = throwable
+                .and_then(|throwable| {
+                    env.call_method(throwable, "toString", "()Ljava/lang/String;", &[])
+                        .ok()?
+                        .l()
+                        .ok()
+                })
+                .and_then(|s| {
+                    // Keep the `JString` alive for the whole closure body:
+                    // `get_string`'s result borrows it, so converting to an
+                    // owned `String` has to happen before it's dropped.
+                    let message = JString::from(s);
+                    env.get_string(&message).ok().map(|s| s.into())
+                })
+                .unwrap_or_else(|| "<unprintable exception>".to_string());
+            return Err(AndroidError::JavaException(message));
+        }
+        Ok(())
+    }
+}
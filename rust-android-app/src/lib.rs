@@ -1,11 +1,16 @@
 use jni::objects::JClass;
 use jni::JNIEnv;
 
+#[cfg(target_os = "android")]
+pub mod android;
+pub mod channel;
+pub mod context;
+pub mod logging;
+pub mod telemetry;
+
 #[no_mangle]
 pub extern "system" fn Java_com_example_rustapp_NativeLib_hello(_env: JNIEnv, _class: JClass) {
-    android_logger::init_once(
-        android_logger::Config::default().with_max_level(log::LevelFilter::Debug),
-    );
+    logging::init();
     log::info!("Hello from Rust!");
 }
 
@@ -0,0 +1,222 @@
+//! Routes `log` records to `android.util.Log` under per-module tags, with
+//! levels tunable at runtime from the Java side, replacing the single
+//! global `android_logger::init_once(... Debug)` call the crate used to
+//! make.
+//!
+//! Each module gets its own logcat tag (its crate name, e.g. `gpui`,
+//! `wgpu_core`, or the app's own crate) instead of everything being tagged
+//! `RustStdoutStderr`, and `android.util.Log.isLoggable` is consulted
+//! before formatting a record so logcat's own level filter is honored
+//! rather than bypassed.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use jni::objects::{JClass, JString};
+use jni::JNIEnv;
+use log::{Level, LevelFilter, Metadata, Record};
+
+/// Per-tag level overrides pushed from Java via
+/// [`Java_com_example_rustapp_NativeLib_setLogLevel`]. A tag
+/// absent from this map falls back to `DEFAULT_LEVEL`.
+static LEVEL_OVERRIDES: RwLock<Option<HashMap<String, LevelFilter>>> = RwLock::new(None);
+
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+
+struct AndroidLogger;
+
+impl log::Log for AndroidLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= level_for(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let tag = module_tag(record.target());
+        let priority = to_android_priority(record.level());
+        if !android_is_loggable(&tag, priority) {
+            return;
+        }
+        android_log_print(priority, &tag, &format!("{}", record.args()));
+    }
+
+    fn flush(&self) {}
+}
+
+fn level_for(target: &str) -> LevelFilter {
+    let tag = module_tag(target);
+    LEVEL_OVERRIDES
+        .read()
+        .expect("log level overrides lock poisoned")
+        .as_ref()
+        .and_then(|overrides| overrides.get(&tag).copied())
+        .unwrap_or(DEFAULT_LEVEL)
+}
+
+/// Android tags cap at 23 characters and read better as the crate name
+/// (`gpui`, `wgpu_core`) than the fully-qualified module path `log`
+/// defaults to.
+fn module_tag(target: &str) -> String {
+    target.split("::").next().unwrap_or(target).to_string()
+}
+
+fn to_android_priority(level: Level) -> i32 {
+    // Matches `android/log.h`'s `android_LogPriority`.
+    match level {
+        Level::Error => 6,
+        Level::Warn => 5,
+        Level::Info => 4,
+        Level::Debug => 3,
+        Level::Trace => 2,
+    }
+}
+
+/// Calls `android.util.Log.isLoggable(tag, priority)` so logcat's own
+/// per-tag level filter (`setprop log.tag.<TAG> <LEVEL>`) is respected on
+/// top of our own override table.
+fn android_is_loggable(tag: &str, priority: i32) -> bool {
+    with_jni_env(|env| {
+        let jtag = env.new_string(tag).ok()?;
+        let result = env
+            .call_static_method(
+                "android/util/Log",
+                "isLoggable",
+                "(Ljava/lang/String;I)Z",
+                &[
+                    jni::objects::JValue::from(&jtag),
+                    jni::objects::JValue::from(priority),
+                ],
+            )
+            .ok()?
+            .z()
+            .ok()?;
+        Some(result)
+    })
+    .unwrap_or(true)
+}
+
+fn android_log_print(priority: i32, tag: &str, message: &str) {
+    with_jni_env(|env| {
+        let jtag = env.new_string(tag).ok()?;
+        let jmessage = env.new_string(message).ok()?;
+        // Convert to the owned `i32` return value here, inside the
+        // closure: the `JValueOwned` the call returns borrows `env`, which
+        // doesn't outlive this closure.
+        let written = env
+            .call_static_method(
+                "android/util/Log",
+                "println",
+                "(ILjava/lang/String;Ljava/lang/String;)I",
+                &[
+                    jni::objects::JValue::from(priority),
+                    jni::objects::JValue::from(&jtag),
+                    jni::objects::JValue::from(&jmessage),
+                ],
+            )
+            .ok()?
+            .i()
+            .ok()?;
+        Some(written)
+    });
+}
+
+/// Attaches the current thread and hands the resulting `JNIEnv` to `f`, if
+/// the `JavaVM` has been cached yet (see [`crate::channel::JNI_OnLoad`]).
+fn with_jni_env<T>(f: impl FnOnce(&mut JNIEnv) -> Option<T>) -> Option<T> {
+    let vm = crate::channel::cached_vm()?;
+    let mut env = vm.attach_current_thread().ok()?;
+    f(&mut env)
+}
+
+/// Installs the Android logger as the global `log` backend and hooks
+/// `std::panic` so native crashes are printed through the same path (with
+/// a backtrace) instead of being lost to an opaque SIGABRT in logcat.
+pub fn init() {
+    let _ = log::set_boxed_logger(Box::new(AndroidLogger));
+    log::set_max_level(LevelFilter::Trace);
+
+    std::panic::set_hook(Box::new(|info| {
+        let backtrace = std::backtrace::Backtrace::force_capture();
+        log::error!(target: "panic", "{info}\n{backtrace}");
+    }));
+}
+
+/// `Java_com_example_rustapp_NativeLib_setLogLevel`: sets the runtime log
+/// level for `tag`, taking effect on the next log call.
+#[no_mangle]
+pub extern "system" fn Java_com_example_rustapp_NativeLib_setLogLevel(
+    mut env: JNIEnv,
+    _class: JClass,
+    tag: JString,
+    level: JString,
+) {
+    let tag: String = match env.get_string(&tag) {
+        Ok(s) => s.into(),
+        Err(err) => {
+            log::error!("setLogLevel: invalid tag: {err}");
+            return;
+        }
+    };
+    let level: String = match env.get_string(&level) {
+        Ok(s) => s.into(),
+        Err(err) => {
+            log::error!("setLogLevel: invalid level: {err}");
+            return;
+        }
+    };
+    let Ok(level) = level.parse::<LevelFilter>() else {
+        log::error!("setLogLevel: unrecognized level `{level}`");
+        return;
+    };
+
+    set_level_override(tag, level);
+}
+
+/// Sets the runtime level override for `tag`, taking effect on the next
+/// log call. Split out from the JNI export above so it can be exercised
+/// without a `JNIEnv`.
+fn set_level_override(tag: String, level: LevelFilter) {
+    LEVEL_OVERRIDES
+        .write()
+        .expect("log level overrides lock poisoned")
+        .get_or_insert_with(HashMap::new)
+        .insert(tag, level);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_tag_strips_submodule_path() {
+        assert_eq!(module_tag("wgpu_core::device::queue"), "wgpu_core");
+        assert_eq!(module_tag("gpui"), "gpui");
+    }
+
+    #[test]
+    fn to_android_priority_matches_android_log_h() {
+        assert_eq!(to_android_priority(Level::Error), 6);
+        assert_eq!(to_android_priority(Level::Warn), 5);
+        assert_eq!(to_android_priority(Level::Info), 4);
+        assert_eq!(to_android_priority(Level::Debug), 3);
+        assert_eq!(to_android_priority(Level::Trace), 2);
+    }
+
+    #[test]
+    fn level_for_falls_back_to_default_for_unknown_tag() {
+        assert_eq!(level_for("logging_tests_unknown_tag::module"), DEFAULT_LEVEL);
+    }
+
+    #[test]
+    fn level_for_honors_override_set_via_set_level_override() {
+        // A tag unique to this test so it doesn't race other tests
+        // sharing the same process-wide `LEVEL_OVERRIDES` map.
+        set_level_override("logging_tests_overridden_tag".to_string(), LevelFilter::Warn);
+        assert_eq!(
+            level_for("logging_tests_overridden_tag::some::module"),
+            LevelFilter::Warn
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use super::ping::Ping;
+
+/// The kinds of metric a caller can record into, mirroring Glean's basic
+/// metric types. There is deliberately no free-text "string" type wide
+/// enough to smuggle identifying data through, and no event type: a metric
+/// carries only its counter, timing or boolean value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetricType {
+    Counter,
+    TimingDistribution,
+    Boolean,
+}
+
+/// A single recorded value, tagged with the metric it belongs to.
+#[derive(Debug, Clone)]
+pub enum MetricValue {
+    /// Adds `delta` to the named counter.
+    Counter(i64),
+    /// Adds one sample (in milliseconds) to the named timing distribution.
+    TimingMillis(u64),
+    /// Sets the named boolean metric to its latest value.
+    Boolean(bool),
+}
+
+/// A metric definition: a name and its type. Nothing registers or checks
+/// these; [`super::record`] accepts any metric name with any value.
+#[derive(Debug, Clone)]
+pub struct Metric {
+    pub name: &'static str,
+    pub kind: MetricType,
+}
+
+impl Metric {
+    pub const fn new(name: &'static str, kind: MetricType) -> Self {
+        Self { name, kind }
+    }
+}
+
+/// Accumulates recorded values in memory between flushes.
+#[derive(Default)]
+pub struct MetricSet {
+    counters: HashMap<String, i64>,
+    timings: HashMap<String, Vec<u64>>,
+    booleans: HashMap<String, bool>,
+}
+
+impl MetricSet {
+    pub fn record(&mut self, metric: &str, value: MetricValue) {
+        match value {
+            MetricValue::Counter(delta) => {
+                *self.counters.entry(metric.to_string()).or_insert(0) += delta;
+            }
+            MetricValue::TimingMillis(sample) => {
+                self.timings.entry(metric.to_string()).or_default().push(sample);
+            }
+            MetricValue::Boolean(value) => {
+                self.booleans.insert(metric.to_string(), value);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.counters.clear();
+        self.timings.clear();
+        self.booleans.clear();
+    }
+
+    /// Snapshots the accumulated values into a ping, or `None` if nothing
+    /// has been recorded since the last flush - an empty ping isn't worth
+    /// persisting or uploading.
+    pub fn drain_into_ping(&self) -> Option<Ping> {
+        if self.counters.is_empty() && self.timings.is_empty() && self.booleans.is_empty() {
+            return None;
+        }
+        Some(Ping {
+            counters: self.counters.clone(),
+            timings_millis: self.timings.clone(),
+            booleans: self.booleans.clone(),
+        })
+    }
+}
+
+/// Parses a `{"metric": "...", "type": "counter"|"timing_ms"|"boolean",
+/// "value": ...}` payload pushed from Kotlin into a metric name and value,
+/// ready for [`super::record`].
+///
+/// Kept free of the global telemetry state so it can be unit tested
+/// without needing [`super::init`] to have run.
+pub fn parse_record_request(bytes: &[u8]) -> Result<Option<(String, MetricValue)>, serde_json::Error> {
+    #[derive(serde::Deserialize)]
+    struct RecordRequest {
+        metric: String,
+        #[serde(rename = "type")]
+        kind: String,
+        value: serde_json::Value,
+    }
+
+    let request: RecordRequest = serde_json::from_slice(bytes)?;
+    let value = match request.kind.as_str() {
+        "counter" => MetricValue::Counter(request.value.as_i64().unwrap_or(1)),
+        "timing_ms" => MetricValue::TimingMillis(request.value.as_u64().unwrap_or(0)),
+        "boolean" => MetricValue::Boolean(request.value.as_bool().unwrap_or(false)),
+        other => {
+            log::warn!("telemetry: unknown metric type `{other}` for `{}`", request.metric);
+            return Ok(None);
+        }
+    };
+    Ok(Some((request.metric, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_accumulates_counters_across_calls() {
+        let mut metrics = MetricSet::default();
+        metrics.record("launches", MetricValue::Counter(1));
+        metrics.record("launches", MetricValue::Counter(2));
+        let ping = metrics.drain_into_ping().expect("non-empty ping");
+        assert_eq!(ping.counters["launches"], 3);
+    }
+
+    #[test]
+    fn record_appends_timing_samples() {
+        let mut metrics = MetricSet::default();
+        metrics.record("startup_ms", MetricValue::TimingMillis(10));
+        metrics.record("startup_ms", MetricValue::TimingMillis(20));
+        let ping = metrics.drain_into_ping().expect("non-empty ping");
+        assert_eq!(ping.timings_millis["startup_ms"], vec![10, 20]);
+    }
+
+    #[test]
+    fn record_overwrites_boolean_with_latest_value() {
+        let mut metrics = MetricSet::default();
+        metrics.record("dark_mode", MetricValue::Boolean(true));
+        metrics.record("dark_mode", MetricValue::Boolean(false));
+        let ping = metrics.drain_into_ping().expect("non-empty ping");
+        assert!(!ping.booleans["dark_mode"]);
+    }
+
+    #[test]
+    fn drain_into_ping_returns_none_when_empty() {
+        let metrics = MetricSet::default();
+        assert!(metrics.drain_into_ping().is_none());
+    }
+
+    #[test]
+    fn clear_removes_everything_recorded() {
+        let mut metrics = MetricSet::default();
+        metrics.record("launches", MetricValue::Counter(1));
+        metrics.clear();
+        assert!(metrics.drain_into_ping().is_none());
+    }
+
+    #[test]
+    fn parse_record_request_reads_counter_payload() {
+        let (metric, value) =
+            parse_record_request(br#"{"metric": "launches", "type": "counter", "value": 1}"#)
+                .unwrap()
+                .expect("recognized metric type");
+        assert_eq!(metric, "launches");
+        assert!(matches!(value, MetricValue::Counter(1)));
+    }
+
+    #[test]
+    fn parse_record_request_ignores_unknown_type() {
+        let result =
+            parse_record_request(br#"{"metric": "x", "type": "histogram", "value": 1}"#).unwrap();
+        assert!(result.is_none());
+    }
+}
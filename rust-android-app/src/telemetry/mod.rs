@@ -0,0 +1,160 @@
+//! An opt-in metrics subsystem modeled on Glean: apps define typed metrics,
+//! record into them from Rust, and the subsystem batches them into pings
+//! that are flushed to a caller-supplied upload callback on a schedule.
+//!
+//! Two things are non-negotiable here because this is telemetry, not a
+//! general event bus: nothing is sent until [`set_upload_enabled`] has been
+//! called with `true`, and disabling it clears everything already queued.
+//! Metric values are plain counters/timings/booleans - there is no
+//! free-text "attach anything" field, so it isn't possible to accidentally
+//! record something identifying through this API.
+
+mod metrics;
+mod ping;
+mod storage;
+
+pub use metrics::{Metric, MetricType, MetricValue};
+
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use crate::context::AndroidContext;
+
+/// Upload callback supplied by the host app: given one serialized ping,
+/// returns whether it was delivered. Pings that fail to send stay queued
+/// and are retried on the next flush.
+pub type UploadCallback = dyn Fn(&[u8]) -> bool + Send + Sync;
+
+struct State {
+    context: Option<AndroidContext>,
+    enabled: bool,
+    metrics: metrics::MetricSet,
+    uploader: Option<Arc<UploadCallback>>,
+}
+
+static STATE: OnceLock<Mutex<State>> = OnceLock::new();
+
+fn state() -> &'static Mutex<State> {
+    STATE.get_or_init(|| {
+        Mutex::new(State {
+            context: None,
+            enabled: false,
+            metrics: metrics::MetricSet::default(),
+            uploader: None,
+        })
+    })
+}
+
+/// Starts the telemetry subsystem: stores `context` (used to read/write
+/// the pending-pings directory), registers `telemetry.record`/
+/// `telemetry.flush` on the JNI [`crate::channel`] so the Kotlin side can
+/// drive both without a bespoke `Java_..._NativeLib_*` export, and, if
+/// `flush_interval` is set, spawns a background thread that flushes on
+/// that cadence. Call this once at startup; recording before this runs is
+/// a no-op since telemetry starts disabled regardless.
+pub fn init(context: AndroidContext, flush_interval: Option<Duration>) {
+    state().lock().expect("telemetry state lock poisoned").context = Some(context);
+
+    crate::channel::register("telemetry.record", |payload| {
+        match metrics::parse_record_request(payload) {
+            Ok(Some((metric, value))) => record(&metric, value),
+            Ok(None) => {}
+            Err(err) => log::error!("telemetry.record: {err}"),
+        }
+        Vec::new()
+    });
+    crate::channel::register("telemetry.flush", |_payload| {
+        flush();
+        Vec::new()
+    });
+
+    if let Some(interval) = flush_interval {
+        std::thread::spawn(move || loop {
+            std::thread::sleep(interval);
+            flush();
+        });
+    }
+}
+
+/// Enables or disables telemetry collection. Disabling clears every
+/// pending metric value and every unsent ping on disk - turning telemetry
+/// back on starts from a clean slate rather than flushing stale data.
+pub fn set_upload_enabled(enabled: bool) {
+    let mut state = state().lock().expect("telemetry state lock poisoned");
+    state.enabled = enabled;
+    if !enabled {
+        state.metrics.clear();
+        if let Some(context) = state.context.as_ref() {
+            if let Err(err) = storage::clear_pending(context) {
+                log::error!("telemetry: failed to clear pending pings: {err}");
+            }
+        }
+    }
+}
+
+/// Registers the callback used to deliver serialized pings. Call this once
+/// at startup before recording any metrics.
+pub fn set_uploader(uploader: impl Fn(&[u8]) -> bool + Send + Sync + 'static) {
+    state().lock().expect("telemetry state lock poisoned").uploader = Some(Arc::new(uploader));
+}
+
+/// Records a sample into `metric`. A no-op when telemetry is disabled, so
+/// call sites don't need their own `if enabled` checks.
+pub fn record(metric: &str, value: MetricValue) {
+    let mut state = state().lock().expect("telemetry state lock poisoned");
+    if !state.enabled {
+        return;
+    }
+    state.metrics.record(metric, value);
+}
+
+/// Persists any unflushed metrics as a new ping and attempts to deliver
+/// every pending ping (including ones left over from a previous process)
+/// through the registered uploader.
+///
+/// Pings that fail to send (no network, uploader returned `false`) remain
+/// on disk in the app's files dir and are retried on the next flush, so
+/// metrics survive both a failed upload and a process death. Call this
+/// from the Android lifecycle's `onPause`/`suspended` path, in addition to
+/// the periodic flush started by [`init`], so pings also go out whenever
+/// the app is backgrounded.
+pub fn flush() {
+    let (context, ping_bytes) = {
+        let mut state = state().lock().expect("telemetry state lock poisoned");
+        if !state.enabled {
+            return;
+        }
+        let Some(context) = state.context.clone() else {
+            log::warn!("telemetry: flush() called before init()");
+            return;
+        };
+        let ping = state.metrics.drain_into_ping();
+        state.metrics.clear();
+        (context, ping.map(|ping| ping::serialize(&ping)))
+    };
+
+    if let Some(bytes) = ping_bytes {
+        if let Err(err) = storage::enqueue(&context, &bytes) {
+            log::error!("telemetry: failed to persist ping: {err}");
+        }
+    }
+
+    let Some(uploader) = state().lock().expect("telemetry state lock poisoned").uploader.clone()
+    else {
+        return;
+    };
+
+    match storage::pending(&context) {
+        Ok(pending) => {
+            for (id, bytes) in pending {
+                if uploader(&bytes) {
+                    if let Err(err) = storage::remove(&context, &id) {
+                        log::error!("telemetry: failed to remove sent ping {id}: {err}");
+                    }
+                }
+            }
+        }
+        Err(err) => log::error!("telemetry: failed to read pending pings: {err}"),
+    }
+}
+
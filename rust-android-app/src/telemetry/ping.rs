@@ -0,0 +1,21 @@
+use std::collections::HashMap;
+
+/// A batch of accumulated metric values, serialized and persisted as a
+/// pending ping, then uploaded (and removed) on this or a later flush.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Ping {
+    pub counters: HashMap<String, i64>,
+    pub timings_millis: HashMap<String, Vec<u64>>,
+    pub booleans: HashMap<String, bool>,
+}
+
+pub fn serialize(ping: &Ping) -> Vec<u8> {
+    // A ping built entirely from our own typed metric values should always
+    // be representable as JSON; a failure here would be a bug in this
+    // module, not a caller error, so fall back to an empty object rather
+    // than panicking on a background flush.
+    serde_json::to_vec(ping).unwrap_or_else(|err| {
+        log::error!("telemetry: failed to serialize ping: {err}");
+        b"{}".to_vec()
+    })
+}
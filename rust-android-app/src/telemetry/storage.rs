@@ -0,0 +1,62 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::context::{AndroidContext, AndroidError};
+
+/// Pending pings are persisted under the app's files dir (not cache,
+/// which the OS can clear under storage pressure) so metrics survive
+/// process death between being recorded and being uploaded.
+const PINGS_SUBDIR: &str = "gpui_mobile_telemetry/pings";
+
+fn pings_dir(context: &AndroidContext) -> io::Result<PathBuf> {
+    let dir = context.files_dir().map_err(to_io_error)?.join(PINGS_SUBDIR);
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Writes `bytes` as a new pending ping file, named so concurrent flushes
+/// from different threads never collide.
+pub fn enqueue(context: &AndroidContext, bytes: &[u8]) -> io::Result<()> {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    let dir = pings_dir(context)?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    fs::write(dir.join(format!("{timestamp}-{id}.json")), bytes)
+}
+
+/// Returns every pending ping as `(id, bytes)`, where `id` is the file
+/// name to pass back to [`remove`] once the ping has been uploaded.
+pub fn pending(context: &AndroidContext) -> io::Result<Vec<(String, Vec<u8>)>> {
+    let dir = pings_dir(context)?;
+    let mut pings = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let bytes = fs::read(entry.path())?;
+        let id = entry.file_name().to_string_lossy().into_owned();
+        pings.push((id, bytes));
+    }
+    Ok(pings)
+}
+
+pub fn remove(context: &AndroidContext, id: &str) -> io::Result<()> {
+    let dir = pings_dir(context)?;
+    fs::remove_file(dir.join(id))
+}
+
+/// Deletes every pending ping, used when telemetry is disabled.
+pub fn clear_pending(context: &AndroidContext) -> io::Result<()> {
+    for (id, _) in pending(context)? {
+        remove(context, &id)?;
+    }
+    Ok(())
+}
+
+fn to_io_error(err: AndroidError) -> io::Error {
+    io::Error::other(err.to_string())
+}